@@ -0,0 +1,110 @@
+use std::sync::{Arc, OnceLock};
+
+use dashmap::DashMap;
+use binaryninja::binaryview::{BinaryView, BinaryViewExt};
+use binaryninja::function::Function;
+use binaryninja::rc::Ref;
+use binaryninja::symbol::Symbol;
+use binaryninja::types::Type;
+use binaryninja::ObjectDestructor;
+use warp::signature::function::{Function as WarpFunction, FunctionGUID};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ViewID(u64);
+
+impl From<&BinaryView> for ViewID {
+    fn from(view: &BinaryView) -> Self {
+        ViewID(view.file().session_id() as u64)
+    }
+}
+
+#[derive(Default)]
+pub struct FunctionCacheEntry {
+    pub cache: DashMap<u64, Ref<Function>>,
+}
+
+#[derive(Default)]
+pub struct GuidCacheEntry {
+    pub cache: DashMap<u64, FunctionGUID>,
+}
+
+#[derive(Default)]
+pub struct MatchedFunctionCacheEntry {
+    pub cache: DashMap<u64, Arc<WarpFunction>>,
+}
+
+/// What WARP markup replaced, so [`crate::plugin::unmatch`] can restore it.
+#[derive(Clone)]
+pub struct MarkupRecord {
+    /// The symbol the function had before WARP matched it, or `None` if it only had an
+    /// auto-generated name (so undoing the match should undefine the user symbol entirely).
+    pub prior_symbol: Option<Ref<Symbol>>,
+    /// The type the function had before WARP matched it, or `None` if it only had an
+    /// analysis-derived type.
+    pub prior_type: Option<Ref<Type>>,
+}
+
+#[derive(Default)]
+pub struct MarkupCacheEntry {
+    pub cache: DashMap<u64, MarkupRecord>,
+}
+
+pub static FUNCTION_CACHE: OnceLock<DashMap<ViewID, FunctionCacheEntry>> = OnceLock::new();
+pub static GUID_CACHE: OnceLock<DashMap<ViewID, GuidCacheEntry>> = OnceLock::new();
+pub static MATCHED_FUNCTION_CACHE: OnceLock<DashMap<ViewID, MatchedFunctionCacheEntry>> =
+    OnceLock::new();
+/// Per-view record of what WARP markup overwrote, keyed by function start address, so a match
+/// can be undone without guessing at what the function looked like before it was applied.
+pub static MARKUP_CACHE: OnceLock<DashMap<ViewID, MarkupCacheEntry>> = OnceLock::new();
+
+pub struct CacheDestructor {}
+
+impl ObjectDestructor for CacheDestructor {
+    fn destruct_view(&self, view: &BinaryView) {
+        let view_id = ViewID::from(view);
+        if let Some(cache) = FUNCTION_CACHE.get() {
+            cache.remove(&view_id);
+        }
+        if let Some(cache) = GUID_CACHE.get() {
+            cache.remove(&view_id);
+        }
+        if let Some(cache) = MATCHED_FUNCTION_CACHE.get() {
+            cache.remove(&view_id);
+        }
+        if let Some(cache) = MARKUP_CACHE.get() {
+            cache.remove(&view_id);
+        }
+    }
+}
+
+pub fn register_cache_destructor() {
+    binaryninja::register_object_destructor(CacheDestructor {});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markup_cache_entry_keeps_first_snapshot_on_rematch() {
+        // `on_matched_function` relies on `.entry(addr).or_insert(record)` so re-running the
+        // matcher over an already-matched function can't clobber the true pre-match baseline
+        // with WARP's own previously-applied symbol/type.
+        let entry = MarkupCacheEntry::default();
+        entry.cache.entry(0x1000).or_insert(MarkupRecord {
+            prior_symbol: None,
+            prior_type: None,
+        });
+        let first_is_some = entry.cache.get(&0x1000).map(|r| r.prior_type.is_some());
+
+        entry
+            .cache
+            .entry(0x1000)
+            .or_insert_with(|| panic!("second snapshot should never be constructed"));
+
+        assert_eq!(
+            entry.cache.get(&0x1000).map(|r| r.prior_type.is_some()),
+            first_is_some
+        );
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
+
+use dashmap::DashMap;
+use memmap2::Mmap;
+use binaryninja::binaryview::{BinaryView, BinaryViewExt};
+use binaryninja::function::Function as BNFunction;
+use binaryninja::llil::{LowLevelILFunction, LowLevelILInstructionKind};
+use binaryninja::platform::Platform;
+use binaryninja::rc::Ref;
+use binaryninja::settings::Settings;
+use warp::signature::function::{Function as WarpFunction, FunctionGUID};
+use warp::signature::Data;
+
+use crate::cache::{ViewID, GUID_CACHE};
+
+/// Per-platform matcher cache, populated from the platform's default signature set and
+/// extended as additional signature files are loaded.
+pub static PLAT_MATCHER_CACHE: OnceLock<DashMap<PlatformID, Matcher>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlatformID(u64);
+
+impl From<&Platform> for PlatformID {
+    fn from(platform: &Platform) -> Self {
+        PlatformID(platform.type_id() as u64)
+    }
+}
+
+impl From<Ref<Platform>> for PlatformID {
+    fn from(platform: Ref<Platform>) -> Self {
+        PlatformID::from(platform.as_ref())
+    }
+}
+
+/// Why a [`MatchCandidate`] was considered a match for a local function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchReason {
+    /// The local function's whole-function GUID is present in the candidate's GUID bucket.
+    ExactGuid,
+    /// No exact GUID bucket existed; the candidate was found by overlapping basic block hashes.
+    PartialBasicBlockHash,
+    /// No exact GUID bucket existed; the candidate was found via shared referenced constants.
+    ConstantOverlap,
+}
+
+/// A scored, ranked candidate produced by [`Matcher::fuzzy_candidates`].
+#[derive(Debug, Clone)]
+pub struct MatchCandidate {
+    pub function: Arc<WarpFunction>,
+    /// Similarity in `[0.0, 1.0]`, higher is more confident.
+    pub score: f32,
+    pub reason: MatchReason,
+}
+
+/// Setting key for the user-configurable minimum similarity score a [`MatchCandidate`] must
+/// clear before WARP will apply markup for it. Registered with Binary Ninja's settings system in
+/// `CorePluginInit`.
+pub const FUZZY_THRESHOLD_SETTING: &str = "warp.fuzzyMatchThreshold";
+
+/// Fallback used when [`FUZZY_THRESHOLD_SETTING`] hasn't been set (or fails to parse).
+pub const DEFAULT_FUZZY_THRESHOLD: f32 = 0.7;
+
+/// Reads the configurable fuzzy match threshold for `view`, so users can loosen or tighten
+/// fuzzy matching per-binary via the `warp.fuzzyMatchThreshold` setting instead of it being a
+/// hardcoded constant.
+pub fn fuzzy_threshold(view: &BinaryView) -> f32 {
+    Settings::new("default")
+        .get_double(FUZZY_THRESHOLD_SETTING, Some(view), None)
+        .map(|value| value as f32)
+        .unwrap_or(DEFAULT_FUZZY_THRESHOLD)
+}
+
+/// A GUID-indexed function record whose body hasn't been parsed out of a memory-mapped
+/// signature file yet. Resolved in place the first time a consumer needs the function's basic
+/// blocks or type, not merely its GUID -- see [`CandidateEntry`].
+#[derive(Clone)]
+struct MappedFunction {
+    mmap: Arc<Mmap>,
+    offset: usize,
+    len: usize,
+}
+
+impl MappedFunction {
+    fn resolve(&self) -> Arc<WarpFunction> {
+        let bytes = &self.mmap[self.offset..self.offset + self.len];
+        Arc::new(
+            WarpFunction::from_bytes(bytes)
+                .expect("GUID index offset pointed at a valid function record"),
+        )
+    }
+}
+
+/// One entry in a [`Matcher`] GUID bucket: either already parsed, or still only indexed from a
+/// memory-mapped signature file. Keeping both states in the same map (rather than a separate
+/// "pending" map consulted first) means resolving a bucket is a single write-lock acquisition
+/// on that shard instead of a check-then-remove-then-reinsert across two maps, which would
+/// otherwise race two callers resolving the same GUID at once.
+#[derive(Clone)]
+enum CandidateEntry {
+    Resolved(Arc<WarpFunction>),
+    Mapped(MappedFunction),
+}
+
+impl CandidateEntry {
+    fn resolve(&self) -> Arc<WarpFunction> {
+        match self {
+            CandidateEntry::Resolved(function) => function.clone(),
+            CandidateEntry::Mapped(mapped) => mapped.resolve(),
+        }
+    }
+}
+
+pub struct Matcher {
+    candidates: DashMap<FunctionGUID, Vec<CandidateEntry>>,
+    pub types: DashMap<String, Arc<warp::signature::types::ComputedType>>,
+}
+
+impl Matcher {
+    pub fn from_platform(platform: Ref<Platform>) -> Self {
+        match platform.default_signature_data() {
+            Some(data) => Self::from_data(data),
+            None => Self {
+                candidates: DashMap::new(),
+                types: DashMap::new(),
+            },
+        }
+    }
+
+    pub fn from_data(data: Data) -> Self {
+        let candidates = DashMap::new();
+        for function in data.functions {
+            candidates
+                .entry(function.guid)
+                .or_insert_with(Vec::new)
+                .push(CandidateEntry::Resolved(Arc::new(function)));
+        }
+        let types = DashMap::new();
+        for ty in data.types {
+            types.insert(ty.name.clone(), Arc::new(ty));
+        }
+        Self { candidates, types }
+    }
+
+    /// Indexes the function GUIDs of an already memory-mapped signature file without parsing any function bodies,
+    /// so loading a multi-hundred-MB corpus signature file only costs a header/index scan up
+    /// front. Full function records are faulted in lazily, one GUID bucket at a time, via
+    /// [`Matcher::get`]. Types are a much smaller section of the file and are parsed eagerly so
+    /// `WARP\Debug\Apply Signature File Types` doesn't need its own lazy-loading path.
+    pub fn from_mmap(mmap: Mmap) -> Self {
+        let mmap = Arc::new(mmap);
+        let candidates = DashMap::new();
+        for entry in Data::index_guids(&mmap) {
+            candidates
+                .entry(entry.guid)
+                .or_insert_with(Vec::new)
+                .push(CandidateEntry::Mapped(MappedFunction {
+                    mmap: mmap.clone(),
+                    offset: entry.offset,
+                    len: entry.len,
+                }));
+        }
+        let types = DashMap::new();
+        for ty in Data::parse_types(&mmap) {
+            types.insert(ty.name.clone(), Arc::new(ty));
+        }
+        Self { candidates, types }
+    }
+
+    /// Looks up every possible match for `guid`, resolving (and caching) any still-mapped
+    /// entries the first time they're asked for. Resolution happens under the single write
+    /// lock `DashMap::get_mut` takes on the bucket's shard, so two callers racing on the same
+    /// GUID can't both observe it as mapped and redundantly (or inconsistently) resolve it.
+    pub fn get(&self, guid: &FunctionGUID) -> Option<Vec<Arc<WarpFunction>>> {
+        let mut bucket = self.candidates.get_mut(guid)?;
+        let resolved: Vec<Arc<WarpFunction>> = bucket
+            .iter_mut()
+            .map(|entry| {
+                let function = entry.resolve();
+                *entry = CandidateEntry::Resolved(function.clone());
+                function
+            })
+            .collect();
+        Some(resolved)
+    }
+
+    /// Number of distinct GUID buckets, whether already parsed or still only indexed.
+    pub fn function_count(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Forces every bucket to be parsed. Used before a full scan (e.g.
+    /// [`Matcher::fuzzy_candidates`]) that needs every candidate's body regardless of GUID.
+    fn resolve_all(&self) {
+        let guids: Vec<FunctionGUID> = self.candidates.iter().map(|entry| *entry.key()).collect();
+        for guid in guids {
+            self.get(&guid);
+        }
+    }
+
+    pub fn extend_with_matcher(&mut self, other: Matcher) {
+        for (guid, entries) in other.candidates {
+            self.candidates.entry(guid).or_default().extend(entries);
+        }
+        for (name, ty) in other.types {
+            self.types.insert(name, ty);
+        }
+    }
+
+    /// Fall back to similarity scoring when `func`'s GUID has no exact bucket. Candidates are drawn
+    /// from every bucket in the matcher and ranked by a Jaccard-like ratio of shared basic
+    /// block hashes plus shared referenced constants; candidates below `threshold` are dropped.
+    ///
+    /// This is deliberately a full scan, and it is not a rare-case cost: against a large generic
+    /// corpus (e.g. a signature file loaded via [`Matcher::from_mmap`]) most local functions
+    /// won't have an exact GUID hit, so the first fuzzy lookup after such a load forces every
+    /// still-mapped candidate to be parsed via [`Matcher::resolve_all`] -- the full-corpus memory
+    /// spike that lazy GUID indexing was meant to avoid, just deferred to the first miss instead
+    /// of happening at load time.
+    pub fn fuzzy_candidates(&self, func: &WarpFunction, threshold: f32) -> Vec<MatchCandidate> {
+        // Unlike `get`, which only faults in the one GUID bucket it was asked for, a fuzzy scan
+        // has to compare against every candidate, so there's no way to keep this lazy.
+        self.resolve_all();
+
+        let local_blocks = basic_block_hash_set(func);
+        let local_constants = referenced_constant_set(func);
+
+        let mut candidates: Vec<MatchCandidate> = self
+            .candidates
+            .iter()
+            .flat_map(|entry| entry.value().iter().map(CandidateEntry::resolve).collect::<Vec<_>>())
+            .filter_map(|candidate| {
+                let (score, reason) = similarity_score(
+                    &local_blocks,
+                    &local_constants,
+                    &basic_block_hash_set(&candidate),
+                    &referenced_constant_set(&candidate),
+                );
+                if score < threshold {
+                    return None;
+                }
+                Some(MatchCandidate {
+                    function: candidate,
+                    score,
+                    reason,
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        candidates
+    }
+
+    /// Picks the most likely candidate out of a GUID bucket holding more than one possible
+    /// match, using each candidate's recorded callee GUIDs (`constraints.call_targets`) scored
+    /// against `local_callees`. Every candidate in `candidates` shares the same whole-function
+    /// GUID by construction (that's why they're all in one bucket), so ties break on the
+    /// candidate's symbol name instead, which is independent of load/insertion order -- picking
+    /// the last element of a tie would otherwise depend on the order signature files were
+    /// merged in via `extend_with_matcher`.
+    pub fn disambiguate<'a>(
+        &self,
+        candidates: &'a [Arc<WarpFunction>],
+        local_callees: &HashSet<FunctionGUID>,
+    ) -> Option<&'a Arc<WarpFunction>> {
+        let scores: Vec<(usize, &str)> = candidates
+            .iter()
+            .map(|candidate| {
+                let callee_overlap = candidate
+                    .constraints
+                    .call_targets
+                    .iter()
+                    .filter(|guid| local_callees.contains(guid))
+                    .count();
+                (callee_overlap, candidate.symbol.name.as_str())
+            })
+            .collect();
+        pick_best(&scores).map(|index| &candidates[index])
+    }
+}
+
+/// Picks the index of the highest-(overlap, name) entry in `scores`, breaking ties on the
+/// lexicographically first name so the result doesn't depend on slice order.
+fn pick_best(scores: &[(usize, &str)]) -> Option<usize> {
+    scores
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, (overlap, name))| (*overlap, std::cmp::Reverse(*name)))
+        .map(|(index, _)| index)
+}
+
+/// Resolves the GUIDs of the functions `function` directly calls, using LLIL call targets
+/// already resolved in the current view. A callee's GUID is computed once and then reused via
+/// [`GUID_CACHE`] (the same cache `WARP\Debug\Cache` reports on), since the same callee is
+/// often shared by many callers in a single disambiguation pass.
+pub fn local_callee_guids(function: &BNFunction, llil: &LowLevelILFunction) -> HashSet<FunctionGUID> {
+    let view = function.view();
+    let view_id = ViewID::from(view.as_ref());
+    let guid_cache = GUID_CACHE.get_or_init(Default::default);
+
+    llil.instructions()
+        .filter_map(|instr| match instr.kind() {
+            LowLevelILInstructionKind::Call(op) | LowLevelILInstructionKind::TailCall(op) => {
+                op.target().as_constant()
+            }
+            _ => None,
+        })
+        .filter_map(|target_address| {
+            if let Some(cached) = guid_cache
+                .get(&view_id)
+                .and_then(|entry| entry.cache.get(&target_address).map(|guid| *guid))
+            {
+                return Some(cached);
+            }
+            let callee = view.functions_at(target_address).into_iter().next()?;
+            let callee_llil = callee.low_level_il().ok()?;
+            let callee_guid = crate::build_function(&callee, &callee_llil).guid;
+            guid_cache
+                .entry(view_id)
+                .or_default()
+                .cache
+                .insert(target_address, callee_guid);
+            Some(callee_guid)
+        })
+        .collect()
+}
+
+fn basic_block_hash_set(function: &WarpFunction) -> HashSet<FunctionGUID> {
+    function
+        .basic_blocks
+        .iter()
+        .map(|block| block.guid)
+        .collect()
+}
+
+fn referenced_constant_set(function: &WarpFunction) -> HashSet<u64> {
+    function.constraints.referenced_constants.iter().copied().collect()
+}
+
+fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.union(b).count();
+    intersection as f32 / union as f32
+}
+
+/// Scores one candidate against the local function and reports which signal actually drove that
+/// candidate's score, per-candidate -- not a single reason reused for every candidate in a scan,
+/// since the signal that dominates can differ from one candidate to the next even at the same
+/// threshold.
+fn similarity_score(
+    local_blocks: &HashSet<FunctionGUID>,
+    local_constants: &HashSet<u64>,
+    other_blocks: &HashSet<FunctionGUID>,
+    other_constants: &HashSet<u64>,
+) -> (f32, MatchReason) {
+    let block_score = jaccard(local_blocks, other_blocks);
+    let constant_score = jaccard(local_constants, other_constants);
+    // Compare each signal's *weighted* contribution (matching `weighted_similarity`'s 0.75/0.25
+    // split), not the raw Jaccard ratios, so the reported reason reflects what actually moved
+    // this candidate's score.
+    let reason = if block_score * 0.75 >= constant_score * 0.25 {
+        MatchReason::PartialBasicBlockHash
+    } else {
+        MatchReason::ConstantOverlap
+    };
+    (weighted_similarity(block_score, constant_score), reason)
+}
+
+/// Combines a basic-block-hash Jaccard ratio and a referenced-constant Jaccard ratio into one
+/// similarity score. Basic block overlap is a much stronger signal than shared constants alone.
+fn weighted_similarity(block_score: f32, constant_score: f32) -> f32 {
+    block_score * 0.75 + constant_score * 0.25
+}
+
+pub fn invalidate_function_matcher_cache() {
+    if let Some(cache) = PLAT_MATCHER_CACHE.get() {
+        cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jaccard_disjoint_sets_score_zero() {
+        let a: HashSet<u32> = [1, 2].into_iter().collect();
+        let b: HashSet<u32> = [3, 4].into_iter().collect();
+        assert_eq!(jaccard(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn jaccard_identical_sets_score_one() {
+        let a: HashSet<u32> = [1, 2, 3].into_iter().collect();
+        assert_eq!(jaccard(&a, &a.clone()), 1.0);
+    }
+
+    #[test]
+    fn jaccard_both_empty_scores_zero_not_nan() {
+        let empty: HashSet<u32> = HashSet::new();
+        assert_eq!(jaccard(&empty, &empty), 0.0);
+    }
+
+    #[test]
+    fn jaccard_partial_overlap() {
+        let a: HashSet<u32> = [1, 2, 3].into_iter().collect();
+        let b: HashSet<u32> = [2, 3, 4].into_iter().collect();
+        // intersection {2, 3} / union {1, 2, 3, 4}
+        assert_eq!(jaccard(&a, &b), 0.5);
+    }
+
+    #[test]
+    fn weighted_similarity_favors_basic_block_overlap_over_constants() {
+        assert!(weighted_similarity(1.0, 0.0) > weighted_similarity(0.0, 1.0));
+    }
+
+    #[test]
+    fn weighted_similarity_identical_candidates_score_one() {
+        assert_eq!(weighted_similarity(1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn similarity_score_reason_follows_constant_overlap_when_no_blocks() {
+        let local_blocks: HashSet<FunctionGUID> = HashSet::new();
+        let local_constants: HashSet<u64> = [1, 2].into_iter().collect();
+        let other_blocks: HashSet<FunctionGUID> = HashSet::new();
+        let other_constants: HashSet<u64> = [1, 2].into_iter().collect();
+        // No basic blocks on either side, so the only signal present is constant overlap --
+        // must be reported as such even though block overlap is weighted higher in general.
+        let (_, reason) = similarity_score(&local_blocks, &local_constants, &other_blocks, &other_constants);
+        assert_eq!(reason, MatchReason::ConstantOverlap);
+    }
+
+    #[test]
+    fn similarity_score_reason_varies_per_candidate() {
+        let local_blocks: HashSet<FunctionGUID> = HashSet::new();
+        let local_constants: HashSet<u64> = [1, 2].into_iter().collect();
+
+        // Candidate A: no block overlap, full constant overlap -> driven by constants.
+        let (_, reason_a) = similarity_score(
+            &local_blocks,
+            &local_constants,
+            &HashSet::new(),
+            &local_constants,
+        );
+        assert_eq!(reason_a, MatchReason::ConstantOverlap);
+
+        // Candidate B: full block overlap and no constants on either side -> driven by blocks,
+        // so it must not report the same reason as candidate A just because it ran in the same
+        // `fuzzy_candidates` call.
+        let guid = FunctionGUID::from_bytes(&[0xaa, 0xbb, 0xcc, 0xdd]);
+        let local_blocks_b: HashSet<FunctionGUID> = [guid].into_iter().collect();
+        let (_, reason_b) = similarity_score(
+            &local_blocks_b,
+            &HashSet::new(),
+            &local_blocks_b,
+            &HashSet::new(),
+        );
+        assert_eq!(reason_b, MatchReason::PartialBasicBlockHash);
+        assert_ne!(reason_a, reason_b);
+    }
+
+    #[test]
+    fn pick_best_prefers_higher_overlap_over_name() {
+        let scores = [(1, "zzz"), (2, "aaa")];
+        assert_eq!(pick_best(&scores), Some(1));
+    }
+
+    #[test]
+    fn pick_best_breaks_ties_on_name_not_position() {
+        // Both tied on overlap; the lexicographically first name should win regardless of
+        // which slot it's in, so this isn't sensitive to signature-file load order.
+        let scores = [(0, "zzz"), (0, "aaa")];
+        assert_eq!(pick_best(&scores), Some(1));
+
+        let scores = [(0, "aaa"), (0, "zzz")];
+        assert_eq!(pick_best(&scores), Some(0));
+    }
+
+    #[test]
+    fn pick_best_empty_returns_none() {
+        let scores: [(usize, &str); 0] = [];
+        assert_eq!(pick_best(&scores), None);
+    }
+}
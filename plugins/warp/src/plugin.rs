@@ -2,10 +2,15 @@ use log::LevelFilter;
 
 use crate::{build_function, cache};
 use crate::cache::{
-    register_cache_destructor, ViewID, FUNCTION_CACHE, GUID_CACHE, MATCHED_FUNCTION_CACHE,
+    register_cache_destructor, MarkupRecord, ViewID, FUNCTION_CACHE, GUID_CACHE,
+    MATCHED_FUNCTION_CACHE, MARKUP_CACHE,
 };
 use crate::convert::{to_bn_symbol_at_address, to_bn_type};
-use crate::matcher::{invalidate_function_matcher_cache, Matcher, PlatformID, PLAT_MATCHER_CACHE};
+use crate::matcher::{
+    fuzzy_threshold, invalidate_function_matcher_cache, local_callee_guids, MatchReason, Matcher,
+    PlatformID, FUZZY_THRESHOLD_SETTING, PLAT_MATCHER_CACHE,
+};
+use binaryninja::settings::Settings;
 use binaryninja::binaryview::{BinaryView, BinaryViewExt};
 use binaryninja::command::{Command, FunctionCommand};
 use binaryninja::function::{Function, FunctionUpdateType};
@@ -18,7 +23,9 @@ mod apply;
 mod copy;
 mod create;
 mod find;
+mod import;
 mod types;
+mod unmatch;
 mod workflow;
 
 // TODO: This icon is a little much
@@ -33,8 +40,33 @@ fn get_warp_tag_type(view: &BinaryView) -> Ref<TagType> {
 // What happens to the function when it is matched.
 // TODO: add user: bool
 // TODO: Rename to markup_function or something.
-pub fn on_matched_function(function: &Function, matched: &WarpFunction) {
+pub fn on_matched_function(
+    function: &Function,
+    matched: &WarpFunction,
+    reason: MatchReason,
+    confidence: f32,
+) {
     let view = function.view();
+
+    // Remember what markup is about to overwrite so `UnmatchFunction`/`RemoveAllMarkup` can put
+    // the function back the way they found it instead of just clearing the symbol/type.
+    let prior_symbol = function.symbol();
+    let prior_type = function.function_type();
+    let record = MarkupRecord {
+        prior_symbol: (!prior_symbol.auto()).then(|| prior_symbol.clone()),
+        prior_type: function.has_user_type().then(|| prior_type.clone()),
+    };
+    // Only the first snapshot for a given function sticks: re-running the matcher over an
+    // already-matched function must not clobber the true pre-match baseline with WARP's own
+    // previously-applied symbol/type.
+    let markup_cache = MARKUP_CACHE.get_or_init(Default::default);
+    markup_cache
+        .entry(ViewID::from(view.as_ref()))
+        .or_default()
+        .cache
+        .entry(function.start())
+        .or_insert(record);
+
     view.define_user_symbol(&to_bn_symbol_at_address(
         &view,
         &matched.symbol,
@@ -44,7 +76,7 @@ pub fn on_matched_function(function: &Function, matched: &WarpFunction) {
     // TODO: Add metadata. (both binja metadata and warp metadata)
     function.add_tag(
         &get_warp_tag_type(&view),
-        matched.guid.to_string(),
+        format!("{} ({reason:?}, {:.0}% confidence)", matched.guid, confidence * 100.0),
         None,
         true,
         None,
@@ -70,7 +102,7 @@ impl FunctionCommand for DebugFunction {
 struct DebugMatcher;
 
 impl FunctionCommand for DebugMatcher {
-    fn action(&self, _view: &BinaryView, function: &Function) {
+    fn action(&self, view: &BinaryView, function: &Function) {
         let Ok(llil) = function.low_level_il() else {
             log::error!("No LLIL for function 0x{:x}", function.start());
             return;
@@ -79,10 +111,32 @@ impl FunctionCommand for DebugMatcher {
         // Build the matcher every time this is called to make sure we arent in a bad state.
         let matcher = Matcher::from_platform(platform);
         let func = build_function(function, &llil);
-        if let Some(possible_matches) = matcher.functions.get(&func.guid) {
-            log::info!("{:#?}", possible_matches.value());
+        if let Some(possible_matches) = matcher.get(&func.guid) {
+            if possible_matches.len() > 1 {
+                let local_callees = local_callee_guids(function, &llil);
+                match matcher.disambiguate(&possible_matches, &local_callees) {
+                    Some(chosen) => log::info!(
+                        "Disambiguated {} possible matches to {:#?} via call-graph overlap",
+                        possible_matches.len(),
+                        chosen
+                    ),
+                    None => log::info!("{:#?}", possible_matches),
+                }
+            } else {
+                log::info!("{:#?}", possible_matches);
+            }
         } else {
-            log::error!("No possible matches found for the function 0x{:x}", function.start());
+            let candidates = matcher.fuzzy_candidates(&func, fuzzy_threshold(view));
+            if candidates.is_empty() {
+                log::error!("No possible matches found for the function 0x{:x}", function.start());
+            } else {
+                log::info!(
+                    "No exact match for function 0x{:x}, {} fuzzy candidate(s) above threshold:",
+                    function.start(),
+                    candidates.len()
+                );
+                log::info!("{:#?}", candidates);
+            }
         };
     }
 
@@ -115,7 +169,7 @@ impl Command for DebugCache {
         if let Some(plat) = view.default_platform() {
             let platform_id = PlatformID::from(plat);
             if let Some(cache) = plat_cache.get(&platform_id) {
-                log::info!("Platform functions: {}", cache.functions.len());
+                log::info!("Platform functions: {}", cache.function_count());
                 log::info!("Platform types: {}", cache.types.len());
             }
         }
@@ -149,6 +203,18 @@ pub extern "C" fn CorePluginInit() -> bool {
     // Make sure caches are flushed when the views get destructed.
     register_cache_destructor();
 
+    Settings::new("default").register_setting_json(
+        FUZZY_THRESHOLD_SETTING,
+        r#"{
+            "title" : "WARP Fuzzy Match Threshold",
+            "type" : "number",
+            "default" : 0.7,
+            "description" : "Minimum similarity score (0.0-1.0) a fuzzy candidate must clear before WARP applies markup for it. Lower values match more loosely; raise this if WARP is applying low-confidence signatures.",
+            "minValue" : 0.0,
+            "maxValue" : 1.0
+        }"#,
+    );
+
     workflow::insert_workflow();
 
     binaryninja::command::register(
@@ -206,6 +272,24 @@ pub extern "C" fn CorePluginInit() -> bool {
         create::CreateSignatureFile {},
     );
 
+    binaryninja::command::register(
+        "WARP\\Import Signatures",
+        "Converts a Ghidra Function ID database or a WARP pattern list (not real FLIRT) into WARP signatures",
+        import::ImportSignatureFile {},
+    );
+
+    binaryninja::command::register_for_function(
+        "WARP\\Unmatch Function",
+        "Removes WARP markup from this function and restores what it overwrote",
+        unmatch::UnmatchFunction {},
+    );
+
+    binaryninja::command::register(
+        "WARP\\Remove All Markup",
+        "Removes WARP markup from every matched function in the view",
+        unmatch::RemoveAllMarkup {},
+    );
+
     // binaryninja::command::register(
     //     "WARP\\Apply Signature File",
     //     "Applies a signature file to the current view",
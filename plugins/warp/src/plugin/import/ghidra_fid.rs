@@ -0,0 +1,87 @@
+use binaryninja::platform::Platform;
+use warp::signature::function::{Function, FunctionGUID};
+use warp::signature::Data;
+
+use super::SignatureImporter;
+
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+/// Imports Ghidra Function ID databases (`.fidb`), which are SQLite databases keyed by a
+/// full/specific hash pair over each function's instruction bytes with relocations masked out.
+///
+/// Neither hash is our GUID, so each imported function's GUID is synthesized by hashing the
+/// function id database's own full-hash bytes; this is stable across imports of the same
+/// `.fidb` but is not comparable to GUIDs computed by WARP itself or by pattern-list import.
+pub struct GhidraFidImporter;
+
+impl SignatureImporter for GhidraFidImporter {
+    fn detect(bytes: &[u8]) -> bool {
+        // The SQLite magic alone just means "some SQLite database" -- plenty of unrelated
+        // files share it. Only claim this format once the FID-specific `functions` table is
+        // actually present.
+        bytes.starts_with(SQLITE_MAGIC) && has_function_table(bytes)
+    }
+
+    fn convert(bytes: &[u8], _platform: &Platform) -> Option<Data> {
+        let entries = read_function_records(bytes)?;
+        let functions = entries
+            .into_iter()
+            .map(|entry| Function {
+                guid: FunctionGUID::from_bytes(&entry.full_hash),
+                symbol: entry.name.into(),
+                ..Default::default()
+            })
+            .collect();
+        Some(Data {
+            functions,
+            types: Vec::new(),
+        })
+    }
+}
+
+struct FunctionRecord {
+    name: String,
+    full_hash: Vec<u8>,
+}
+
+/// Checks the database's own schema for a `functions` table rather than trusting the SQLite
+/// magic bytes alone, so an arbitrary `.sqlite`/`.db` file doesn't get silently routed into
+/// `read_function_records` and fail (or worse, partially succeed against an unrelated schema).
+fn has_function_table(bytes: &[u8]) -> bool {
+    let Ok(mut connection) = rusqlite::Connection::open_in_memory() else {
+        return false;
+    };
+    if connection
+        .deserialize(rusqlite::MAIN_DB, bytes.to_vec(), false)
+        .is_err()
+    {
+        return false;
+    }
+    connection
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'functions'",
+            [],
+            |_| Ok(()),
+        )
+        .is_ok()
+}
+
+/// Reads the `functions` table of a Function ID database. Ghidra's `.fidb` schema additionally
+/// tracks per-library metadata (compiler, language id) that isn't modeled yet; only the
+/// name/full-hash pair needed to seed a WARP match is pulled out here.
+fn read_function_records(bytes: &[u8]) -> Option<Vec<FunctionRecord>> {
+    let mut connection = rusqlite::Connection::open_in_memory().ok()?;
+    connection
+        .deserialize(rusqlite::MAIN_DB, bytes.to_vec(), false)
+        .ok()?;
+    let mut statement = connection.prepare("SELECT name, full_hash FROM functions").ok()?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok(FunctionRecord {
+                name: row.get(0)?,
+                full_hash: row.get(1)?,
+            })
+        })
+        .ok()?;
+    Some(rows.filter_map(Result::ok).collect())
+}
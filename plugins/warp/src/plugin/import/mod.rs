@@ -0,0 +1,110 @@
+use binaryninja::binaryview::{BinaryView, BinaryViewExt};
+use binaryninja::command::Command;
+use binaryninja::interaction::{get_choice_input, get_open_filename_input};
+use binaryninja::platform::Platform;
+use warp::signature::Data;
+
+use crate::matcher::{Matcher, PlatformID, PLAT_MATCHER_CACHE};
+
+mod ghidra_fid;
+mod pattern_list;
+
+use ghidra_fid::GhidraFidImporter;
+use pattern_list::PatternListImporter;
+
+/// Converts a foreign signature database into WARP [`Data`] for a given [`Platform`].
+///
+/// Implementations synthesize GUIDs for functions the source format doesn't already provide
+/// one for (the pattern-list format and Ghidra Function ID both key on pattern/name, not our
+/// GUID scheme), so imported matches should be treated as lower-confidence than native `.sbin`
+/// signatures.
+pub trait SignatureImporter {
+    /// Cheap sniff of `bytes` to see if this importer understands the format.
+    fn detect(bytes: &[u8]) -> bool
+    where
+        Self: Sized;
+
+    /// Parse `bytes` and emit WARP signature data scoped to `platform`.
+    fn convert(bytes: &[u8], platform: &Platform) -> Option<Data>
+    where
+        Self: Sized;
+}
+
+struct Format {
+    name: &'static str,
+    detect: fn(&[u8]) -> bool,
+    convert: fn(&[u8], &Platform) -> Option<Data>,
+}
+
+const FORMATS: &[Format] = &[
+    Format {
+        name: "WARP Pattern List (.wpl, not real FLIRT)",
+        detect: PatternListImporter::detect,
+        convert: PatternListImporter::convert,
+    },
+    Format {
+        name: "Ghidra Function ID (.fidb)",
+        detect: GhidraFidImporter::detect,
+        convert: GhidraFidImporter::convert,
+    },
+];
+
+pub struct ImportSignatureFile;
+
+impl Command for ImportSignatureFile {
+    fn action(&self, view: &BinaryView) {
+        let Some(platform) = view.default_platform() else {
+            log::error!("Default platform must be set to import a signature file!");
+            return;
+        };
+
+        let Some(file) = get_open_filename_input("Import Signature File", "*.wpl;*.fidb") else {
+            return;
+        };
+
+        let Ok(bytes) = std::fs::read(&file) else {
+            log::error!("Could not read signature file: {:?}", file);
+            return;
+        };
+
+        let names: Vec<&str> = FORMATS.iter().map(|format| format.name).collect();
+        let default = FORMATS
+            .iter()
+            .position(|format| (format.detect)(&bytes))
+            .unwrap_or(0);
+        let Some(choice) = get_choice_input("Import Signature File", "Format", &names) else {
+            return;
+        };
+        let format = FORMATS.get(choice).unwrap_or(&FORMATS[default]);
+
+        let Some(data) = (format.convert)(&bytes, platform.as_ref()) else {
+            log::error!("Could not convert {} signature file: {:?}", format.name, file);
+            return;
+        };
+
+        let new_matcher = Matcher::from_data(data);
+        log::info!(
+            "Imported {} with {} functions and {} types...",
+            format.name,
+            new_matcher.function_count(),
+            new_matcher.types.len()
+        );
+
+        let platform_id = PlatformID::from(platform.as_ref());
+        let matcher_cache = PLAT_MATCHER_CACHE.get_or_init(Default::default);
+        match matcher_cache.get_mut(&platform_id) {
+            Some(mut matcher) => matcher.extend_with_matcher(new_matcher),
+            None => {
+                // We still must uphold `from_platform` in case we are running this before the matcher workflow
+                // is kicked off. Other-wise we only will have the `new_matcher` data.
+                let mut matcher = Matcher::from_platform(platform);
+                matcher.extend_with_matcher(new_matcher);
+                matcher_cache.insert(platform_id, matcher);
+            }
+        }
+    }
+
+    fn valid(&self, _view: &BinaryView) -> bool {
+        true
+    }
+}
@@ -0,0 +1,182 @@
+use binaryninja::platform::Platform;
+use warp::signature::function::{Function, FunctionGUID};
+use warp::signature::Data;
+
+use super::SignatureImporter;
+
+/// Not an IDA FLIRT magic number. FLIRT `.sig` files start with `IDASGN`; this importer reads a
+/// different, WARP-specific container so it never mistakes (or gets mistaken for) a real FLIRT
+/// signature file -- see [`PatternListImporter`] for why.
+const PATTERN_LIST_MAGIC: &[u8] = b"WARPPLST";
+
+/// Imports a simplified, WARP-specific pattern-list format: **not** the real IDA FLIRT binary
+/// format (which is a length-prefixed pattern-tree with CRC16 tails and wildcard bitmasks).
+/// There is no FLIRT-compatible importer in this plugin yet; a from-scratch reimplementation of
+/// that parser is tracked separately. This exists as a demo/staging format -- hand-author a
+/// `.wpl` file with [`PATTERN_LIST_MAGIC`] to seed WARP with wildcarded patterns -- and is
+/// intentionally keyed off a magic number real `.sig` files don't have, so it never silently
+/// "detects" (and then fails to convert) a genuine FLIRT signature file.
+///
+/// Patterns are wildcarded byte sequences rather than our whole-function GUID, so each imported
+/// function's GUID is synthesized by hashing all of the pattern's fixed (non-wildcard) bytes.
+/// This is enough to seed an exact-GUID bucket for binaries compiled with the exact toolchain
+/// the pattern was authored against; it does not do FLIRT-style wildcard/crc16 tail matching for
+/// binaries that drift from that toolchain.
+pub struct PatternListImporter;
+
+impl SignatureImporter for PatternListImporter {
+    fn detect(bytes: &[u8]) -> bool {
+        bytes.starts_with(PATTERN_LIST_MAGIC)
+    }
+
+    fn convert(bytes: &[u8], _platform: &Platform) -> Option<Data> {
+        let records = parse_pattern_records(bytes)?;
+        let functions = records
+            .into_iter()
+            .map(|record| Function {
+                guid: FunctionGUID::from_bytes(&record.fixed_bytes),
+                symbol: record.name.into(),
+                ..Default::default()
+            })
+            .collect();
+        Some(Data {
+            functions,
+            types: Vec::new(),
+        })
+    }
+}
+
+struct PatternRecord {
+    name: String,
+    fixed_bytes: Vec<u8>,
+}
+
+/// Walks the leaf pattern nodes of a pattern-list file, pulling out each record's name and its
+/// fixed (non-`?`) bytes, wherever they sit in the pattern. A file can chain multiple records
+/// with identical fixed bytes; those collapse naturally once re-hashed into WARP's GUID space.
+///
+/// This only understands the simplified `[pattern_len][pattern_bytes][name_len][name_bytes]`
+/// record stream that follows the magic and a version byte -- it is not a FLIRT parser. To avoid silently importing
+/// garbage from a file that merely starts with [`PATTERN_LIST_MAGIC`], every structural
+/// assumption is checked and any violation fails the whole parse rather than returning whatever
+/// was read so far: a zero-length pattern or name, a name that isn't printable non-space ASCII, a pattern
+/// that's nothing but wildcards, or a trailing partial record that doesn't exactly consume the
+/// rest of the file.
+fn parse_pattern_records(bytes: &[u8]) -> Option<Vec<PatternRecord>> {
+    if bytes.len() < PATTERN_LIST_MAGIC.len() + 1 {
+        return None;
+    }
+    let mut records = Vec::new();
+    let mut cursor = PATTERN_LIST_MAGIC.len();
+    // Version byte, then pattern nodes. Nodes are `[pattern_len][pattern_bytes][name_len][name_bytes]`.
+    cursor += 1;
+    while cursor < bytes.len() {
+        let &pattern_len = bytes.get(cursor)?;
+        cursor += 1;
+        if pattern_len == 0 {
+            return None;
+        }
+        let pattern_end = cursor.checked_add(pattern_len as usize)?;
+        let fixed_bytes: Vec<u8> = bytes
+            .get(cursor..pattern_end)?
+            .iter()
+            .copied()
+            .filter(|&b| b != b'?')
+            .collect();
+        cursor = pattern_end;
+        if fixed_bytes.is_empty() {
+            return None;
+        }
+
+        let &name_len = bytes.get(cursor)?;
+        cursor += 1;
+        if name_len == 0 {
+            return None;
+        }
+        let name_end = cursor.checked_add(name_len as usize)?;
+        let name_bytes = bytes.get(cursor..name_end)?;
+        cursor = name_end;
+
+        let name = std::str::from_utf8(name_bytes).ok()?;
+        if !name.chars().all(|c| c.is_ascii_graphic()) {
+            return None;
+        }
+
+        records.push(PatternRecord {
+            name: name.to_string(),
+            fixed_bytes,
+        });
+    }
+
+    if cursor != bytes.len() || records.is_empty() {
+        return None;
+    }
+    Some(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern_list_file(records: &[(&[u8], &str)]) -> Vec<u8> {
+        let mut bytes = PATTERN_LIST_MAGIC.to_vec();
+        bytes.push(1); // version
+        for (pattern, name) in records {
+            bytes.push(pattern.len() as u8);
+            bytes.extend_from_slice(pattern);
+            bytes.push(name.len() as u8);
+            bytes.extend_from_slice(name.as_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn parses_well_formed_records() {
+        let bytes = pattern_list_file(&[(b"\xaa\xbb?\xcc", "memcpy"), (b"\x90\x90", "nop_slide")]);
+        let records = parse_pattern_records(&bytes).expect("well-formed file should parse");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "memcpy");
+        assert_eq!(records[0].fixed_bytes, vec![0xaa, 0xbb, 0xcc]);
+        assert_eq!(records[1].name, "nop_slide");
+    }
+
+    #[test]
+    fn rejects_file_with_only_the_magic() {
+        assert!(parse_pattern_records(PATTERN_LIST_MAGIC).is_none());
+    }
+
+    #[test]
+    fn rejects_trailing_garbage_after_the_last_record() {
+        let mut bytes = pattern_list_file(&[(b"\xaa\xbb", "memcpy")]);
+        bytes.push(0xff);
+        assert!(parse_pattern_records(&bytes).is_none());
+    }
+
+    #[test]
+    fn rejects_all_wildcard_pattern() {
+        let bytes = pattern_list_file(&[(b"??", "memcpy")]);
+        assert!(parse_pattern_records(&bytes).is_none());
+    }
+
+    #[test]
+    fn rejects_non_printable_name() {
+        let bytes = pattern_list_file(&[(b"\xaa\xbb", "\x01\x02")]);
+        assert!(parse_pattern_records(&bytes).is_none());
+    }
+
+    #[test]
+    fn rejects_arbitrary_bytes_that_merely_start_with_the_magic() {
+        let mut bytes = PATTERN_LIST_MAGIC.to_vec();
+        bytes.extend_from_slice(&[0u8; 32]);
+        assert!(parse_pattern_records(&bytes).is_none());
+    }
+
+    #[test]
+    fn does_not_detect_a_real_flirt_sig_file() {
+        // A genuine FLIRT file starts with `IDASGN`, not our `WARPPLST` marker -- this importer
+        // must never claim to handle it, since it can't actually parse the real format.
+        let mut real_flirt_like = b"IDASGN".to_vec();
+        real_flirt_like.extend_from_slice(&[0u8; 32]);
+        assert!(!PatternListImporter::detect(&real_flirt_like));
+    }
+}
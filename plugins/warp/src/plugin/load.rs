@@ -16,20 +16,25 @@ impl Command for LoadSignatureFile {
             return;
         };
 
-        let Ok(data) = std::fs::read(&file) else {
-            log::error!("Could not read signature file: {:?}", file);
+        let Ok(handle) = std::fs::File::open(&file) else {
+            log::error!("Could not open signature file: {:?}", file);
             return;
         };
 
-        let Some(data) = warp::signature::Data::from_bytes(&data) else {
-            log::error!("Could not get data from signature file: {:?}", file);
-            return;
+        // Corpus signature files can run into the hundreds of MB; mmap it and let `Matcher`
+        // index GUIDs lazily instead of reading the whole thing into a heap `Vec<u8>` up front.
+        let mmap = match unsafe { memmap2::Mmap::map(&handle) } {
+            Ok(mmap) => mmap,
+            Err(err) => {
+                log::error!("Could not map signature file {:?}: {err}", file);
+                return;
+            }
         };
 
-        let new_matcher = Matcher::from_data(data);
+        let new_matcher = Matcher::from_mmap(mmap);
         log::info!(
             "Loading signature file with {} functions and {} types...",
-            new_matcher.functions.len(),
+            new_matcher.function_count(),
             new_matcher.types.len()
         );
         let platform_id = PlatformID::from(platform.as_ref());
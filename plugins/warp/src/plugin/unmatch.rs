@@ -0,0 +1,87 @@
+use binaryninja::binaryview::{BinaryView, BinaryViewExt};
+use binaryninja::command::{Command, FunctionCommand};
+use binaryninja::function::Function;
+
+use crate::cache::{ViewID, MARKUP_CACHE};
+
+use super::{get_warp_tag_type, TAG_NAME};
+
+/// Removes WARP markup from a single function, restoring whatever symbol/type it had before
+/// WARP matched it.
+pub struct UnmatchFunction;
+
+impl FunctionCommand for UnmatchFunction {
+    fn action(&self, view: &BinaryView, function: &Function) {
+        unmatch_function(view, function);
+    }
+
+    fn valid(&self, view: &BinaryView, function: &Function) -> bool {
+        has_warp_tag(view, function)
+    }
+}
+
+/// Removes WARP markup from every matched function in the view.
+pub struct RemoveAllMarkup;
+
+impl Command for RemoveAllMarkup {
+    fn action(&self, view: &BinaryView) {
+        let functions: Vec<_> = view
+            .functions()
+            .iter()
+            .filter(|function| has_warp_tag(view, function))
+            .collect();
+        log::info!("Removing WARP markup from {} function(s)...", functions.len());
+        for function in functions {
+            unmatch_function(view, &function);
+        }
+    }
+
+    fn valid(&self, _view: &BinaryView) -> bool {
+        true
+    }
+}
+
+fn has_warp_tag(view: &BinaryView, function: &Function) -> bool {
+    let Some(tag_type) = view.get_tag_type(TAG_NAME) else {
+        return false;
+    };
+    function
+        .function_tags()
+        .iter()
+        .any(|tag| tag.t_type() == tag_type)
+}
+
+/// Deletes the `WARP` tag from `function` and reverts the user symbol/type it set, using
+/// whatever was recorded for this function in [`MARKUP_CACHE`] when it was matched. If nothing
+/// was recorded (e.g. the markup predates this cache, or the view was reloaded), this falls
+/// back to simply clearing the WARP tag and undoing the user symbol/type outright.
+fn unmatch_function(view: &BinaryView, function: &Function) {
+    let tag_type = get_warp_tag_type(view);
+    for tag in function.function_tags() {
+        if tag.t_type() == tag_type {
+            function.remove_user_tag(&tag);
+        }
+    }
+
+    let view_id = ViewID::from(view);
+    let record = MARKUP_CACHE
+        .get_or_init(Default::default)
+        .get(&view_id)
+        .and_then(|entry| entry.cache.remove(&function.start()))
+        .map(|(_, record)| record);
+    let (prior_symbol, prior_type) = match record {
+        Some(record) => (record.prior_symbol, record.prior_type),
+        None => (None, None),
+    };
+
+    match prior_symbol {
+        Some(symbol) => view.define_user_symbol(&symbol),
+        None => view.undefine_user_symbol(&function.symbol()),
+    }
+    match prior_type {
+        Some(ty) => function.set_user_type(&ty),
+        None => function.set_auto_type(None),
+    }
+
+    function.mark_updates_required(binaryninja::function::FunctionUpdateType::FullAutoFunctionUpdate);
+}
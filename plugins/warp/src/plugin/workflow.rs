@@ -0,0 +1,68 @@
+use crate::matcher::{
+    fuzzy_threshold, local_callee_guids, MatchReason, Matcher, PlatformID, PLAT_MATCHER_CACHE,
+};
+use crate::{build_function, on_matched_function};
+use binaryninja::binaryview::{BinaryView, BinaryViewExt};
+use binaryninja::command::Command;
+
+/// Matches every analyzed function in the view against the platform's signature set and
+/// applies markup for whatever it finds: an exact GUID hit (disambiguated via call-graph
+/// overlap if the bucket holds more than one candidate), or otherwise the best fuzzy candidate
+/// above the `warp.fuzzyMatchThreshold` setting (see [`fuzzy_threshold`]).
+pub struct RunMatcher;
+
+impl Command for RunMatcher {
+    fn action(&self, view: &BinaryView) {
+        let Some(platform) = view.default_platform() else {
+            log::error!("Default platform must be set to run the matcher!");
+            return;
+        };
+
+        let threshold = fuzzy_threshold(view);
+        let platform_id = PlatformID::from(platform.as_ref());
+        let matcher_cache = PLAT_MATCHER_CACHE.get_or_init(Default::default);
+        if matcher_cache.get(&platform_id).is_none() {
+            matcher_cache.insert(platform_id, Matcher::from_platform(platform.clone()));
+        }
+        let matcher = matcher_cache.get(&platform_id).unwrap();
+
+        for function in view.functions().iter() {
+            let Ok(llil) = function.low_level_il() else {
+                continue;
+            };
+            let func = build_function(&function, &llil);
+            match matcher.get(&func.guid) {
+                Some(possible_matches) if possible_matches.len() == 1 => {
+                    on_matched_function(&function, &possible_matches[0], MatchReason::ExactGuid, 1.0);
+                }
+                Some(possible_matches) => {
+                    let local_callees = local_callee_guids(&function, &llil);
+                    if let Some(chosen) = matcher.disambiguate(&possible_matches, &local_callees) {
+                        on_matched_function(&function, chosen, MatchReason::ExactGuid, 1.0);
+                    }
+                }
+                None => {
+                    let candidates = matcher.fuzzy_candidates(&func, threshold);
+                    if let Some(best) = candidates.first() {
+                        on_matched_function(&function, &best.function, best.reason, best.score);
+                    }
+                }
+            }
+        }
+    }
+
+    fn valid(&self, _view: &BinaryView) -> bool {
+        true
+    }
+}
+
+/// Hooks `RunMatcher` into the default analysis workflow so matches apply as soon as a
+/// function's analysis settles, without the user needing to invoke `WARP\Run Matcher` by hand.
+pub fn insert_workflow() {
+    binaryninja::workflow::Workflow::instance("core.function.metaAnalysis")
+        .register_activity_after(
+            "core.function.translateTailCalls",
+            "extension.warp.runMatcher",
+            |view: &BinaryView| RunMatcher {}.action(view),
+        );
+}